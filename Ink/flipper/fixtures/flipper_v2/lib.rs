@@ -0,0 +1,226 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+//! Catálogo gravado no layout da versão 2 do armazenamento, com um único gênero fixo por filme.
+//!
+//! Usado apenas pelos testes e2e de `upgrade`: os nomes da estrutura e dos campos definem as
+//! chaves do armazenamento e precisam continuar iguais aos do contrato `flipper`.
+
+pub use self::flipper::{Data, Flipper, FlipperRef, Genero};
+
+#[ink::contract]
+mod flipper {
+    use ink::prelude::string::String;
+    use ink::storage::Mapping;
+    use scale::{Decode, Encode};
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Genero {
+        Acao,
+        Animacao,
+        Comedia,
+        Drama,
+        Gospel,
+        Suspense,
+        Outros,
+    }
+
+    /// Data codificada como a tupla (ano, mês, dia).
+    #[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Data {
+        pub ano: u32,
+        pub mes: u8,
+        pub dia: u8,
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum StatusFilme {
+        Ativo,
+        Arquivado,
+        Removido,
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Filme {
+        id: u32,
+        nome: String,
+        bilhetes_vendidos: u32,
+        lancamento: Data,
+        genero: Genero,
+        status: StatusFilme,
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Role {
+        Admin,
+        Curator,
+        BoxOfficeReporter,
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum TipoRegistro {
+        SaldoInicial,
+        Venda,
+        Correcao { registro_corrigido: Option<u32> },
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RegistroVenda {
+        tipo: TipoRegistro,
+        quantidade: i64,
+        data: Option<Data>,
+        reportado_por: AccountId,
+        bloco: BlockNumber,
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Revisao {
+        bloco: BlockNumber,
+        autor: AccountId,
+        anterior: Option<Filme>,
+        atual: Filme,
+    }
+
+    /// Mesmos campos, na mesma ordem, da estrutura raiz do `flipper` na versão 2.
+    #[ink(storage)]
+    pub struct Flipper {
+        filmes: Mapping<u32, Filme>,
+        ids_por_nome: Mapping<String, u32>,
+        ids_filmes: Mapping<u32, u32>,
+        posicao_filmes: Mapping<u32, u32>,
+        total_filmes: u32,
+        proximo_id: u32,
+        owner: AccountId,
+        roles: Mapping<(AccountId, Role), ()>,
+        registros_venda: Mapping<(u32, u32), RegistroVenda>,
+        ano_minimo_lancamento: u32,
+        anos_a_frente: u32,
+        total_registros_venda: Mapping<u32, u32>,
+        vendas_por_dia: Mapping<(u32, u32), u32>,
+        pausado: bool,
+        storage_version: u32,
+        cursor_migracao: u32,
+        revisoes: Mapping<(u32, u32), Revisao>,
+        total_revisoes: Mapping<u32, u32>,
+    }
+
+    impl Default for Flipper {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                filmes: Mapping::default(),
+                ids_por_nome: Mapping::default(),
+                ids_filmes: Mapping::default(),
+                posicao_filmes: Mapping::default(),
+                total_filmes: 0,
+                proximo_id: 1,
+                owner: Self::env().caller(),
+                roles: Mapping::default(),
+                registros_venda: Mapping::default(),
+                ano_minimo_lancamento: 2000,
+                anos_a_frente: 2,
+                total_registros_venda: Mapping::default(),
+                vendas_por_dia: Mapping::default(),
+                pausado: false,
+                storage_version: 2,
+                cursor_migracao: 0,
+                revisoes: Mapping::default(),
+                total_revisoes: Mapping::default(),
+            }
+        }
+
+        /// Cadastra um filme ativo, com o saldo inicial no livro de vendas e a revisão do cadastro.
+        #[ink(message)]
+        pub fn add_filme(&mut self, nome: String, bilhetes_vendidos: u32, lancamento: Data, genero: Genero) -> u32 {
+            let id = self.proximo_id;
+            self.proximo_id += 1;
+            let filme = Filme { id, nome, bilhetes_vendidos, lancamento, genero, status: StatusFilme::Ativo };
+            self.ids_por_nome.insert(&filme.nome, &id);
+            self.filmes.insert(id, &filme);
+            self.ids_filmes.insert(self.total_filmes, &id);
+            self.posicao_filmes.insert(id, &self.total_filmes);
+            self.total_filmes += 1;
+            self.anexa_registro_venda(id, TipoRegistro::SaldoInicial, i64::from(bilhetes_vendidos));
+            self.registra_revisao(None, &filme);
+            id
+        }
+
+        /// Corrige o total de bilhetes vendidos, registrando a correção e uma nova revisão.
+        #[ink(message)]
+        pub fn set_bilhetes_vendidos(&mut self, id: u32, bilhetes_vendidos: u32) {
+            let anterior = self.filmes.get(id).expect("Filme não cadastrado");
+            let mut filme = anterior.clone();
+            filme.bilhetes_vendidos = bilhetes_vendidos;
+            self.filmes.insert(id, &filme);
+            let ajuste = i64::from(bilhetes_vendidos) - i64::from(anterior.bilhetes_vendidos);
+            self.anexa_registro_venda(id, TipoRegistro::Correcao { registro_corrigido: None }, ajuste);
+            self.registra_revisao(Some(anterior), &filme);
+        }
+
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) {
+            assert_eq!(self.env().caller(), self.owner, "Apenas o dono pode atualizar");
+            self.env().set_code_hash(&code_hash).expect("Código não encontrado");
+        }
+
+        fn anexa_registro_venda(&mut self, id: u32, tipo: TipoRegistro, quantidade: i64) {
+            let indice = self.total_registros_venda.get(id).unwrap_or(0);
+            self.registros_venda.insert((id, indice), &RegistroVenda {
+                tipo,
+                quantidade,
+                data: None,
+                reportado_por: self.env().caller(),
+                bloco: self.env().block_number(),
+            });
+            self.total_registros_venda.insert(id, &(indice + 1));
+        }
+
+        fn registra_revisao(&mut self, anterior: Option<Filme>, atual: &Filme) {
+            let indice = self.total_revisoes.get(atual.id).unwrap_or(0);
+            self.revisoes.insert((atual.id, indice), &Revisao {
+                bloco: self.env().block_number(),
+                autor: self.env().caller(),
+                anterior,
+                atual: atual.clone(),
+            });
+            self.total_revisoes.insert(atual.id, &(indice + 1));
+        }
+    }
+}